@@ -1,9 +1,12 @@
-use clap::Parser;
-use serde::Deserialize;
+use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 use tokio::fs;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use futures_util::StreamExt;
+use rand::Rng;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::Semaphore;
 
@@ -21,11 +24,67 @@ struct Args {
     #[arg(value_name = "download-dir", default_value = ".")]
     download_dir: String,
 
+    /// Hash algorithm for integrity checks
+    #[arg(long, value_name = "algorithm", value_enum, default_value_t = HashAlg::Sha256)]
+    hash: HashAlg,
+
+    /// Re-hash existing local files against their stored digest and exit
+    #[arg(long)]
+    verify: bool,
+
+    /// Run continuously, repeating the mirror every <seconds> (daemon mode)
+    #[arg(long, value_name = "seconds")]
+    watch: Option<u64>,
+
+    /// Skip crawling and download from the cached enumeration manifest
+    #[arg(long, visible_alias = "use-cached-listing")]
+    resume: bool,
+
+    /// After a fresh enumeration, delete local files no longer on the server
+    #[arg(long)]
+    prune: bool,
+
     /// Debug output (-vv for trace)
     #[arg(short, long, action = clap::ArgAction::Count)]
     verbose: u8,
 }
 
+// ── Hashing ───────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum HashAlg {
+    Sha256,
+    Sha1,
+    Md5,
+}
+
+impl HashAlg {
+    /// File extension Caddy serves the sibling digest under (`<name>.sha256`).
+    fn ext(self) -> &'static str {
+        match self {
+            HashAlg::Sha256 => "sha256",
+            HashAlg::Sha1 => "sha1",
+            HashAlg::Md5 => "md5",
+        }
+    }
+
+    fn hasher(self) -> Box<dyn digest::DynDigest + Send> {
+        match self {
+            HashAlg::Sha256 => Box::new(sha2::Sha256::default()),
+            HashAlg::Sha1 => Box::new(sha1::Sha1::default()),
+            HashAlg::Md5 => Box::new(md5::Md5::default()),
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        s.push_str(&format!("{b:02x}"));
+    }
+    s
+}
+
 // ── Colors ──────────────────────────────────────────────────────────────────
 
 const GREEN: &str = "\x1b[0;32m";
@@ -85,35 +144,87 @@ struct CaddyEntry {
     url: Option<String>,
     #[serde(default)]
     is_dir: bool,
+    /// Byte size Caddy reports per entry; lets us total the tree up front.
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    etag: Option<String>,
+}
+
+// ── File entry ────────────────────────────────────────────────────────────────
+
+/// A file discovered during enumeration: its tree-relative path, absolute URL,
+/// and reported size (0 when the server omits it).
+#[derive(Serialize, Deserialize)]
+struct FileEntry {
+    path: String,
+    url: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    etag: Option<String>,
+}
+
+/// Render a byte count with binary units, the way cargo does with `bytesize`.
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
 }
 
 // ── Metadata ────────────────────────────────────────────────────────────────
 
-async fn read_metadata(path: &Path) -> (Option<String>, Option<String>) {
+#[derive(Default)]
+struct Metadata {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// Hex digest of the last-downloaded body, with its algorithm.
+    digest: Option<String>,
+    hash: Option<String>,
+}
+
+async fn read_metadata(path: &Path) -> Metadata {
     let Ok(content) = fs::read_to_string(path).await else {
-        return (None, None);
+        return Metadata::default();
     };
-    let mut etag = None;
-    let mut last_modified = None;
+    let mut meta = Metadata::default();
     for line in content.lines() {
-        if let Some(v) = line.strip_prefix("etag=") {
-            if !v.is_empty() && v != "null" {
-                etag = Some(v.to_string());
-            }
-        } else if let Some(v) = line.strip_prefix("last_modified=") {
-            if !v.is_empty() && v != "null" {
-                last_modified = Some(v.to_string());
-            }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if value.is_empty() || value == "null" {
+            continue;
+        }
+        let value = value.to_string();
+        match key {
+            "etag" => meta.etag = Some(value),
+            "last_modified" => meta.last_modified = Some(value),
+            "digest" => meta.digest = Some(value),
+            "hash" => meta.hash = Some(value),
+            _ => {}
         }
     }
-    (etag, last_modified)
+    meta
 }
 
-async fn save_metadata(path: &Path, etag: &str, last_modified: &str) {
+async fn save_metadata(path: &Path, etag: &str, last_modified: &str, digest: &str, hash: &str) {
     if let Some(parent) = path.parent() {
         let _ = fs::create_dir_all(parent).await;
     }
-    let _ = fs::write(path, format!("etag={etag}\nlast_modified={last_modified}\n")).await;
+    let _ = fs::write(
+        path,
+        format!("etag={etag}\nlast_modified={last_modified}\ndigest={digest}\nhash={hash}\n"),
+    )
+    .await;
 }
 
 // ── Enumeration ─────────────────────────────────────────────────────────────
@@ -127,7 +238,7 @@ async fn enumerate_files(
     log: &Arc<Logger>,
     counter: &Arc<AtomicUsize>,
     max_concurrent: usize,
-) -> Vec<(String, String)> {
+) -> Vec<FileEntry> {
     // (dir_url, path_prefix)
     let (tx, rx) = async_channel::unbounded::<(String, String)>();
     let in_flight = Arc::new(AtomicUsize::new(1)); // 1 for the seed item
@@ -194,7 +305,12 @@ async fn enumerate_files(
                         let _ = tx.send((full_url, format!("{full_path}/"))).await;
                     } else {
                         log.debug(&format!("Found file: {full_path} -> {full_url}"));
-                        local_files.push((full_path, full_url));
+                        local_files.push(FileEntry {
+                            path: full_path,
+                            url: full_url,
+                            size: entry.size,
+                            etag: entry.etag,
+                        });
                         counter.fetch_add(1, Ordering::Relaxed);
                     }
                 }
@@ -223,6 +339,136 @@ async fn enumerate_files(
     all_files
 }
 
+// ── Enumeration manifest ──────────────────────────────────────────────────────
+
+/// A manifest rejected as stale after this many seconds, forcing a re-crawl.
+const MANIFEST_MAX_AGE_SECS: u64 = 24 * 60 * 60;
+
+/// Cached enumeration state, stamped with the base URL and wall-clock time so a
+/// mismatched or stale listing can be rejected without trusting it.
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    base_url: String,
+    timestamp: u64,
+    files: Vec<FileEntry>,
+}
+
+fn manifest_path(metadata_dir: &Path) -> PathBuf {
+    metadata_dir.join("manifest.json")
+}
+
+fn now_unix() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Serialize the freshly-enumerated listing so the next run can `--resume`.
+async fn save_manifest(metadata_dir: &Path, base_url: &str, files: &[FileEntry], log: &Arc<Logger>) {
+    let manifest = Manifest {
+        base_url: base_url.to_string(),
+        timestamp: now_unix(),
+        files: files
+            .iter()
+            .map(|f| FileEntry {
+                path: f.path.clone(),
+                url: f.url.clone(),
+                size: f.size,
+                etag: f.etag.clone(),
+            })
+            .collect(),
+    };
+    match serde_json::to_string(&manifest) {
+        Ok(json) => {
+            if let Err(e) = fs::write(manifest_path(metadata_dir), json).await {
+                log.error(&format!("Failed to write manifest: {e}"));
+            }
+        }
+        Err(e) => log.error(&format!("Failed to serialize manifest: {e}")),
+    }
+}
+
+/// Load a cached manifest, rejecting it when the base URL differs or it has
+/// aged out past [`MANIFEST_MAX_AGE_SECS`].
+async fn load_manifest(metadata_dir: &Path, base_url: &str, log: &Arc<Logger>) -> Option<Vec<FileEntry>> {
+    let path = manifest_path(metadata_dir);
+    let content = match fs::read_to_string(&path).await {
+        Ok(c) => c,
+        Err(e) => {
+            log.error(&format!("No usable manifest at {}: {e}", path.display()));
+            return None;
+        }
+    };
+    let manifest: Manifest = match serde_json::from_str(&content) {
+        Ok(m) => m,
+        Err(e) => {
+            log.error(&format!("Manifest is corrupt, ignoring: {e}"));
+            return None;
+        }
+    };
+    if manifest.base_url != base_url {
+        log.error(&format!(
+            "Manifest base URL {} != {base_url}, ignoring", manifest.base_url
+        ));
+        return None;
+    }
+    let age = now_unix().saturating_sub(manifest.timestamp);
+    if age > MANIFEST_MAX_AGE_SECS {
+        log.error(&format!("Manifest is {age}s old (stale), ignoring"));
+        return None;
+    }
+    log.info(&format!("Loaded {} files from cached manifest", manifest.files.len()));
+    Some(manifest.files)
+}
+
+/// Delete local files and `.meta` entries that no longer appear on the server,
+/// turning the local tree into a true mirror rather than an append-only copy.
+async fn prune_tree(files: &[FileEntry], download_dir: &Path, metadata_dir: &Path, log: &Arc<Logger>) {
+    let expected: std::collections::HashSet<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    let mut removed = 0usize;
+
+    // Collect local files relative to the download dir, skipping the metadata
+    // directory and in-progress `.tmp` files.
+    let mut stack = vec![download_dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(mut rd) = fs::read_dir(&dir).await else { continue };
+        while let Ok(Some(entry)) = rd.next_entry().await {
+            let path = entry.path();
+            if path == *metadata_dir {
+                continue;
+            }
+            let is_dir = entry.file_type().await.map(|t| t.is_dir()).unwrap_or(false);
+            if is_dir {
+                stack.push(path);
+                continue;
+            }
+            let Ok(rel) = path.strip_prefix(download_dir) else { continue };
+            let rel = rel.to_string_lossy();
+            if rel.ends_with(".tmp") {
+                continue;
+            }
+            if !expected.contains(rel.as_ref()) {
+                log.info(&format!("🗑️  {RED}Pruning{NC}: {rel}"));
+                let _ = fs::remove_file(&path).await;
+                let meta = metadata_dir.join(format!("{rel}.meta"));
+                let _ = fs::remove_file(&meta).await;
+                removed += 1;
+            }
+        }
+    }
+
+    // Sweep `.meta` sidecars for paths the server no longer lists, including
+    // those whose local file is already gone.
+    for entry in enumerate_local_files(metadata_dir).await {
+        if !expected.contains(entry.path.as_str()) {
+            let _ = fs::remove_file(metadata_dir.join(format!("{}.meta", entry.path))).await;
+        }
+    }
+
+    log.info(&format!("Pruned {removed} stale files"));
+}
+
 // ── Spinner ─────────────────────────────────────────────────────────────────
 
 const FRAMES: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
@@ -244,6 +490,72 @@ async fn run_spinner(counter: Arc<AtomicUsize>, stop: tokio::sync::watch::Receiv
     eprint!("\r\x1b[K");
 }
 
+/// Download-phase spinner: renders files done, bytes moved, a rolling
+/// throughput figure, and — when the grand total is known up front — a
+/// percentage-complete and rough ETA.
+async fn run_download_spinner(
+    progress: Arc<AtomicUsize>,
+    bytes: Arc<AtomicU64>,
+    total_files: usize,
+    total_bytes: u64,
+    stop: tokio::sync::watch::Receiver<bool>,
+) {
+    let mut i = 0usize;
+    let mut last_bytes = 0u64;
+    let mut last = std::time::Instant::now();
+    let mut rate = 0.0f64; // bytes/sec, exponentially smoothed
+    loop {
+        if *stop.borrow() {
+            break;
+        }
+        let done = progress.load(Ordering::Relaxed);
+        let moved = bytes.load(Ordering::Relaxed);
+
+        let now = std::time::Instant::now();
+        let elapsed = now.duration_since(last).as_secs_f64();
+        if elapsed >= 0.5 {
+            let instant = (moved.saturating_sub(last_bytes)) as f64 / elapsed;
+            rate = if rate == 0.0 { instant } else { rate * 0.7 + instant * 0.3 };
+            last_bytes = moved;
+            last = now;
+        }
+
+        let mut line = format!(
+            "{GREEN}[INFO]{NC} {} [{done}/{total_files}] {} @ {}/s",
+            FRAMES[i % FRAMES.len()],
+            human_bytes(moved),
+            human_bytes(rate as u64),
+        );
+        if total_bytes > 0 {
+            let pct = (moved as f64 / total_bytes as f64 * 100.0).min(100.0);
+            line.push_str(&format!(" ({pct:.0}% of {}", human_bytes(total_bytes)));
+            if rate > 1.0 {
+                let remaining = total_bytes.saturating_sub(moved) as f64 / rate;
+                line.push_str(&format!(", ETA {}", human_duration(remaining)));
+            }
+            line.push(')');
+        }
+        eprint!("\r\x1b[K{line}");
+        i += 1;
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    }
+    eprint!("\r\x1b[K");
+}
+
+/// Format a rough duration in seconds as `HhMMm`, `MmSSs` or `Ss`, dropping
+/// empty leads; once hours are shown the seconds are left off.
+fn human_duration(secs: f64) -> String {
+    let total = secs.round() as u64;
+    let (h, m, s) = (total / 3600, (total % 3600) / 60, total % 60);
+    if h > 0 {
+        format!("{h}h{m:02}m")
+    } else if m > 0 {
+        format!("{m}m{s:02}s")
+    } else {
+        format!("{s}s")
+    }
+}
+
 // ── Download ────────────────────────────────────────────────────────────────
 
 #[derive(Clone, Copy)]
@@ -253,16 +565,62 @@ enum DlResult {
     Failed,
 }
 
+/// Outcome of a single transfer attempt, distinguishing failures that are
+/// worth retrying from ones that never will be.
+enum Attempt {
+    /// Terminal success — either downloaded or skipped as unmodified.
+    Done(DlResult),
+    /// Transient failure; retry after the optional server-suggested delay.
+    Retryable(Option<std::time::Duration>),
+    /// Permanent failure (e.g. 404/401/403); don't waste further attempts.
+    Fatal,
+}
+
 const MAX_RETRIES: u32 = 3;
-const RETRY_DELAYS: &[u64] = &[500, 2000, 5000]; // ms
+const BACKOFF_BASE_MS: u64 = 500;
+const BACKOFF_CAP_MS: u64 = 30_000;
+
+/// True for statuses that are transient and so worth retrying. 404/401/403 are
+/// deliberately excluded — they won't resolve themselves on a second request.
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 408 | 429 | 500 | 502 | 503 | 504)
+}
+
+/// Full-jitter backoff: for attempt `k`, a random delay in
+/// `[0, min(cap, base * 2^k))`. Randomizing the whole interval desynchronizes
+/// the 50 workers far better than a fixed schedule would.
+fn jittered_backoff(attempt: u32) -> std::time::Duration {
+    let exp = BACKOFF_BASE_MS.saturating_mul(1u64 << attempt.min(20));
+    let ceil = exp.clamp(1, BACKOFF_CAP_MS);
+    let ms = rand::thread_rng().gen_range(0..ceil);
+    std::time::Duration::from_millis(ms)
+}
 
+/// Parse a `Retry-After` value, which is either delta-seconds or an HTTP-date.
+/// The result is clamped to [`BACKOFF_CAP_MS`]: an honest `Retry-After: 3600`
+/// must not park a worker — and the semaphore permit it holds — asleep for an
+/// hour, which a burst of 503s could otherwise use to stall the whole pool.
+fn parse_retry_after(value: &str) -> Option<std::time::Duration> {
+    let value = value.trim();
+    let raw = if let Ok(secs) = value.parse::<u64>() {
+        std::time::Duration::from_secs(secs)
+    } else {
+        let when = httpdate::parse_http_date(value).ok()?;
+        when.duration_since(std::time::SystemTime::now()).ok()?
+    };
+    Some(raw.min(std::time::Duration::from_millis(BACKOFF_CAP_MS)))
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn download_file(
     client: &reqwest::Client,
     file_path: &str,
     url: &str,
     download_dir: &Path,
     metadata_dir: &Path,
+    hash_alg: HashAlg,
     progress: &AtomicUsize,
+    bytes: &AtomicU64,
     total: usize,
     log: &Logger,
 ) -> DlResult {
@@ -274,15 +632,30 @@ async fn download_file(
         let _ = fs::create_dir_all(parent).await;
     }
 
-    // Retry loop with exponential backoff
+    // Retry loop: only transient failures re-enter it, and each wait is
+    // jittered (or honors the server's `Retry-After`) to avoid thundering-herd
+    // retries across the concurrent workers.
+    let mut pending_delay: Option<std::time::Duration> = None;
+    // Bytes this file has contributed to the shared throughput total so far, so
+    // a `200` restart can undo the re-streamed prefix instead of double-counting.
+    let mut counted: u64 = 0;
     for attempt in 0..MAX_RETRIES {
         if attempt > 0 {
-            let delay = RETRY_DELAYS.get(attempt as usize - 1).copied().unwrap_or(5000);
-            log.debug(&format!("[{}/{total}] Retry {attempt} for {file_path} after {delay}ms",
-                progress.load(Ordering::Relaxed) + 1));
-            tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+            let delay = pending_delay
+                .take()
+                .unwrap_or_else(|| jittered_backoff(attempt));
+            log.debug(&format!("[{}/{total}] Retry {attempt} for {file_path} after {}ms",
+                progress.load(Ordering::Relaxed) + 1, delay.as_millis()));
+            tokio::time::sleep(delay).await;
         }
 
+        // Resume an interrupted transfer: if a `.tmp` survived a previous
+        // attempt, pick up from wherever it left off via a ranged request.
+        let resume_from = match fs::metadata(&temp_file).await {
+            Ok(m) => m.len(),
+            Err(_) => 0,
+        };
+
         let result = download_file_once(
             client,
             file_path,
@@ -290,21 +663,214 @@ async fn download_file(
             &local_file,
             &temp_file,
             &meta_file,
+            hash_alg,
+            resume_from,
             progress,
+            bytes,
+            &mut counted,
             total,
             log,
         ).await;
 
+        // Count the file exactly once, when it reaches a terminal outcome —
+        // the per-attempt log lines inside `download_file_once` only *read*
+        // `progress`, so a file that retries before succeeding no longer
+        // bumps the done/total counter (and the percentage and ETA) past the
+        // real totals.
         match result {
-            DlResult::Downloaded | DlResult::Skipped => return result,
-            DlResult::Failed if attempt < MAX_RETRIES - 1 => continue,
-            DlResult::Failed => return DlResult::Failed,
+            Attempt::Done(r) => {
+                progress.fetch_add(1, Ordering::Relaxed);
+                return r;
+            }
+            Attempt::Fatal => {
+                progress.fetch_add(1, Ordering::Relaxed);
+                return DlResult::Failed;
+            }
+            Attempt::Retryable(retry_after) => {
+                pending_delay = retry_after;
+                continue;
+            }
         }
     }
 
+    progress.fetch_add(1, Ordering::Relaxed);
     DlResult::Failed
 }
 
+/// Parse the start offset out of a `Content-Range: bytes START-END/TOTAL`
+/// header, so we can confirm the server resumed from exactly where we asked.
+fn content_range_start(resp: &reqwest::Response) -> Option<u64> {
+    let value = resp.headers().get("content-range")?.to_str().ok()?;
+    parse_content_range_start(value)
+}
+
+/// Pull the start offset out of a `Content-Range` header value.
+fn parse_content_range_start(value: &str) -> Option<u64> {
+    let rest = value.trim().strip_prefix("bytes ")?;
+    let range = rest.split('/').next()?;
+    range.split('-').next()?.trim().parse().ok()
+}
+
+/// Outcome of looking for a file's sibling digest (`<name>.sha256`).
+enum RemoteDigest {
+    /// The server served a digest we can verify against.
+    Found(String),
+    /// There is no sibling digest to check against — a 404, or any other
+    /// client-side status (401/403/…) a proxy or auth layer returns for the
+    /// missing sibling. Verification is simply skipped.
+    Absent,
+    /// The digest could not be fetched for a transient reason (transport error,
+    /// 5xx, unreadable body). Distinct from `Absent` so the caller can re-fetch
+    /// the digest rather than quietly accept the bytes unverified.
+    Unavailable,
+}
+
+/// Fetch the sibling digest Caddy may serve next to a file (`<name>.sha256`).
+/// A missing digest — a 404 or any other client error (a proxy/auth layer may
+/// answer 403 for the absent sibling) — is reported as [`RemoteDigest::Absent`]
+/// (nothing to verify), while a transient failure (transport error or 5xx) is
+/// [`RemoteDigest::Unavailable`] so the caller can re-fetch rather than accept
+/// the bytes unverified. The usual `<hex>  <filename>` layout is accepted, as
+/// is a bare digest.
+async fn fetch_remote_digest(
+    client: &reqwest::Client,
+    url: &str,
+    hash_alg: HashAlg,
+    log: &Logger,
+) -> RemoteDigest {
+    let digest_url = format!("{url}.{}", hash_alg.ext());
+    let resp = match client.get(&digest_url).send().await {
+        Ok(r) => r,
+        Err(e) => {
+            log.debug(&format!("Could not fetch sibling digest {digest_url}: {e}"));
+            return RemoteDigest::Unavailable;
+        }
+    };
+    let status = resp.status();
+    // A client error means the sibling is simply not there to verify against
+    // (404), or is fronted by a proxy/auth layer answering e.g. 403 — retrying
+    // won't conjure a digest, so skip verification rather than failing the file.
+    // Only server errors (5xx) are transient enough to be worth re-fetching.
+    if status.is_client_error() {
+        log.debug(&format!("No sibling digest ({status}) for {digest_url}"));
+        return RemoteDigest::Absent;
+    }
+    if !status.is_success() {
+        log.debug(&format!("HTTP {status} fetching sibling digest {digest_url}"));
+        return RemoteDigest::Unavailable;
+    }
+    let body = match resp.text().await {
+        Ok(b) => b,
+        Err(e) => {
+            log.debug(&format!("Could not read sibling digest {digest_url}: {e}"));
+            return RemoteDigest::Unavailable;
+        }
+    };
+    match body.split_whitespace().next() {
+        Some(digest) => {
+            log.trace(&format!("Fetched digest {digest} from {digest_url}"));
+            RemoteDigest::Found(digest.to_string())
+        }
+        // An empty digest file has nothing to check against.
+        None => RemoteDigest::Absent,
+    }
+}
+
+/// Enumerate the locally-mirrored files from the persisted `.meta` sidecars,
+/// so integrity checks run offline and still cover files that have since been
+/// deleted from the server. Each `.meta` under `metadata_dir` mirrors a file
+/// under the download tree, so stripping the `.meta` suffix recovers its path.
+async fn enumerate_local_files(metadata_dir: &Path) -> Vec<FileEntry> {
+    let mut files = Vec::new();
+    let mut stack = vec![metadata_dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(mut rd) = fs::read_dir(&dir).await else { continue };
+        while let Ok(Some(entry)) = rd.next_entry().await {
+            let path = entry.path();
+            if entry.file_type().await.map(|t| t.is_dir()).unwrap_or(false) {
+                stack.push(path);
+                continue;
+            }
+            let Ok(rel) = path.strip_prefix(metadata_dir) else { continue };
+            let rel = rel.to_string_lossy();
+            if let Some(file_path) = rel.strip_suffix(".meta") {
+                files.push(FileEntry { path: file_path.to_string(), url: String::new(), size: 0, etag: None });
+            }
+        }
+    }
+    files
+}
+
+/// `--verify` mode: re-hash every local file that has a stored digest and
+/// report any whose contents no longer match, catching silent bitrot without
+/// re-downloading anything.
+async fn verify_files(
+    files: &[FileEntry],
+    download_dir: &Path,
+    metadata_dir: &Path,
+    default_alg: HashAlg,
+    log: &Arc<Logger>,
+) -> usize {
+    let mut checked = 0usize;
+    let mut corrupt = 0usize;
+    for entry in files {
+        let file_path = &entry.path;
+        let meta_file = metadata_dir.join(format!("{file_path}.meta"));
+        let meta = read_metadata(&meta_file).await;
+        let Some(expected) = meta.digest else {
+            log.debug(&format!("No stored digest for {file_path}, skipping"));
+            continue;
+        };
+        let alg = match meta.hash.as_deref() {
+            Some("sha1") => HashAlg::Sha1,
+            Some("md5") => HashAlg::Md5,
+            Some("sha256") => HashAlg::Sha256,
+            _ => default_alg,
+        };
+        let local_file = download_dir.join(file_path);
+        // Stream the file through the hasher in fixed-size chunks rather than
+        // slurping it into memory, so verifying a multi-GB mirror stays within
+        // a constant memory budget like the download path.
+        let mut file = match fs::File::open(&local_file).await {
+            Ok(f) => f,
+            Err(e) => {
+                log.error(&format!("Missing/unreadable {file_path}: {e}"));
+                corrupt += 1;
+                continue;
+            }
+        };
+        let mut hasher = alg.hasher();
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut read_err = false;
+        loop {
+            match file.read(&mut buf).await {
+                Ok(0) => break,
+                Ok(n) => hasher.update(&buf[..n]),
+                Err(e) => {
+                    log.error(&format!("Missing/unreadable {file_path}: {e}"));
+                    read_err = true;
+                    break;
+                }
+            }
+        }
+        if read_err {
+            corrupt += 1;
+            continue;
+        }
+        let actual = to_hex(&hasher.finalize());
+        checked += 1;
+        if actual.eq_ignore_ascii_case(&expected) {
+            log.info(&format!("✅ {GREEN}OK{NC}: {file_path}"));
+        } else {
+            log.error(&format!("❌ {RED}CORRUPT{NC}: {file_path} (expected {expected}, got {actual})"));
+            corrupt += 1;
+        }
+    }
+    log.info(&format!("Verification complete: {checked} checked, {corrupt} corrupt"));
+    corrupt
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn download_file_once(
     client: &reqwest::Client,
     file_path: &str,
@@ -312,42 +878,86 @@ async fn download_file_once(
     local_file: &Path,
     temp_file: &Path,
     meta_file: &Path,
+    hash_alg: HashAlg,
+    resume_from: u64,
     progress: &AtomicUsize,
+    bytes: &AtomicU64,
+    counted: &mut u64,
     total: usize,
     log: &Logger,
-) -> DlResult {
+) -> Attempt {
     let mut req = client.get(url);
-    let (cached_etag, cached_lm) = read_metadata(meta_file).await;
-    if let Some(ref etag) = cached_etag {
+    let cached = read_metadata(meta_file).await;
+    if let Some(ref etag) = cached.etag {
         req = req.header("If-None-Match", etag.as_str());
     }
-    if let Some(ref lm) = cached_lm {
+    if let Some(ref lm) = cached.last_modified {
         req = req.header("If-Modified-Since", lm.as_str());
     }
+    // Only resume when we can prove the partial `.tmp` belongs to the same
+    // resource version. We pair the `Range` with an `If-Range: <validator>` so
+    // the server serves `206` (prefix still valid) or falls back to a full
+    // `200` (resource changed). With no stored validator — e.g. a `.tmp` left
+    // by a hard kill before any `.meta` was written — we can't make that
+    // guarantee, so we discard the stale partial and start over rather than
+    // risk concatenating bytes from two different versions.
+    let resume_validator = cached.etag.as_deref().or(cached.last_modified.as_deref());
+    let resume_from = match (resume_from, resume_validator) {
+        (n, Some(validator)) if n > 0 => {
+            log.debug(&format!("Resuming {file_path} from byte {n}"));
+            req = req
+                .header("Range", format!("bytes={n}-"))
+                .header("If-Range", validator);
+            n
+        }
+        (n, None) if n > 0 => {
+            log.debug(&format!(
+                "Discarding unverifiable partial for {file_path} (no stored validator)"
+            ));
+            0
+        }
+        _ => 0,
+    };
 
     log.trace(&format!("GET {url}"));
 
     let resp = match req.send().await {
         Ok(r) => r,
         Err(e) => {
-            let n = progress.fetch_add(1, Ordering::Relaxed) + 1;
+            let n = progress.load(Ordering::Relaxed) + 1;
             log.error(&format!("[{n}/{total}] Failed to download {file_path}: {e}"));
-            return DlResult::Failed;
+            // Connection/timeout errors are transient.
+            return Attempt::Retryable(None);
         }
     };
 
     let status = resp.status();
 
     if status == reqwest::StatusCode::NOT_MODIFIED {
-        let n = progress.fetch_add(1, Ordering::Relaxed) + 1;
+        let _ = fs::remove_file(temp_file).await;
+        let n = progress.load(Ordering::Relaxed) + 1;
         log.info(&format!("[{n}/{total}] ⏭️  {MAGENTA}Unmodified{NC}: {file_path}"));
-        return DlResult::Skipped;
+        return Attempt::Done(DlResult::Skipped);
     }
 
     if !status.is_success() {
-        let n = progress.fetch_add(1, Ordering::Relaxed) + 1;
+        let n = progress.load(Ordering::Relaxed) + 1;
+        if is_retryable_status(status) {
+            // Prefer the server's own pacing hint when it sends one.
+            let retry_after = resp
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(parse_retry_after);
+            log.error(&format!("[{n}/{total}] HTTP {status} for {file_path} (will retry)"));
+            return Attempt::Retryable(retry_after);
+        }
         log.error(&format!("[{n}/{total}] HTTP {status} for {file_path}"));
-        return DlResult::Failed;
+        // Drop any partial left by an earlier attempt: a fatal status means we
+        // give up on this file, and an orphan `.tmp` would linger in the tree
+        // (later `--prune` skips `.tmp`, so nothing else reclaims it).
+        let _ = fs::remove_file(temp_file).await;
+        return Attempt::Fatal;
     }
 
     let etag = resp
@@ -372,33 +982,349 @@ async fn download_file_once(
         }
     }
 
-    let bytes = match resp.bytes().await {
-        Ok(b) => b,
+    // Decide whether we are appending to the partial `.tmp` or starting over.
+    // A `206 Partial Content` means the server honored our `Range` — the
+    // `Content-Range` start must match the length we sent or the two halves
+    // would not line up. A mismatch means the body begins at some other offset,
+    // so we cannot splice it onto our prefix *or* treat it as a whole file;
+    // drop the partial and retry from byte 0. A plain `200 OK` means the range
+    // was ignored, so truncate and redownload.
+    let appending = if resume_from > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT {
+        match content_range_start(&resp) {
+            Some(start) if start == resume_from => true,
+            other => {
+                log.debug(&format!(
+                    "Content-Range start {other:?} != {resume_from} for {file_path}, restarting"
+                ));
+                // Drop the partial and discount the bytes it contributed to the
+                // shared total, so a terminal failure here doesn't leave the
+                // re-sent prefix inflating the throughput/percentage figures.
+                let _ = fs::remove_file(temp_file).await;
+                if *counted > 0 {
+                    bytes.fetch_sub(*counted, Ordering::Relaxed);
+                    *counted = 0;
+                }
+                return Attempt::Retryable(None);
+            }
+        }
+    } else {
+        false
+    };
+
+    // When the server ignored our `Range` (or `If-Range` failed) we re-stream
+    // the file from the top. Discount whatever this file already contributed to
+    // the shared `bytes` total on earlier attempts so the throughput and
+    // percentage figures don't double-count the re-sent prefix.
+    if !appending && *counted > 0 {
+        bytes.fetch_sub(*counted, Ordering::Relaxed);
+        *counted = 0;
+    }
+
+    let open = if appending {
+        fs::OpenOptions::new().append(true).open(temp_file).await
+    } else {
+        fs::File::create(temp_file).await
+    };
+    let mut out = match open {
+        Ok(f) => f,
         Err(e) => {
-            let n = progress.fetch_add(1, Ordering::Relaxed) + 1;
-            log.error(&format!("[{n}/{total}] Failed reading body for {file_path}: {e}"));
-            return DlResult::Failed;
+            let n = progress.load(Ordering::Relaxed) + 1;
+            log.error(&format!("[{n}/{total}] Failed opening temp for {file_path}: {e}"));
+            return Attempt::Retryable(None);
         }
     };
 
-    if let Err(e) = fs::write(temp_file, &bytes).await {
-        let n = progress.fetch_add(1, Ordering::Relaxed) + 1;
-        log.error(&format!("[{n}/{total}] Failed writing {file_path}: {e}"));
-        return DlResult::Failed;
+    // Hash the full body as it lands, the way a torrent client hashes a piece
+    // before accepting it. When resuming, fold the bytes already on disk into
+    // the digest first so it still covers the whole file.
+    let mut hasher = hash_alg.hasher();
+    if appending {
+        // Fold the bytes already on disk into the digest by streaming them back
+        // in fixed-size chunks — reading the whole prefix into memory would
+        // defeat the constant-memory goal of resuming a multi-GB transfer.
+        match fs::File::open(temp_file).await {
+            Ok(mut existing) => {
+                let mut buf = vec![0u8; 64 * 1024];
+                loop {
+                    match existing.read(&mut buf).await {
+                        Ok(0) => break,
+                        Ok(n) => hasher.update(&buf[..n]),
+                        Err(e) => {
+                            log.error(&format!("[{}/{total}] Failed rehashing partial {file_path}: {e}",
+                                progress.load(Ordering::Relaxed) + 1));
+                            return Attempt::Retryable(None);
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                log.error(&format!("[{}/{total}] Failed rehashing partial {file_path}: {e}",
+                    progress.load(Ordering::Relaxed) + 1));
+                return Attempt::Retryable(None);
+            }
+        }
+    }
+
+    // Stream the body straight to disk rather than buffering it in memory, so
+    // multi-GB mirror targets cost a fixed chunk of RAM regardless of size.
+    let mut stream = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(c) => c,
+            Err(e) => {
+                let _ = out.flush().await;
+                let n = progress.load(Ordering::Relaxed) + 1;
+                log.error(&format!("[{n}/{total}] Transfer interrupted for {file_path}: {e}"));
+                // Leave the `.tmp` in place so the next attempt resumes it.
+                return Attempt::Retryable(None);
+            }
+        };
+        hasher.update(&chunk);
+        if let Err(e) = out.write_all(&chunk).await {
+            let n = progress.load(Ordering::Relaxed) + 1;
+            log.error(&format!("[{n}/{total}] Failed writing {file_path}: {e}"));
+            return Attempt::Retryable(None);
+        }
+        bytes.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        *counted += chunk.len() as u64;
+    }
+    if let Err(e) = out.flush().await {
+        let n = progress.load(Ordering::Relaxed) + 1;
+        log.error(&format!("[{n}/{total}] Failed flushing {file_path}: {e}"));
+        // Drop the partial so the next attempt restarts from 0 — a half-flushed
+        // `.tmp` that happens to be full-length would otherwise make the resume
+        // request `Range: bytes=N-` draw a `416` and be marked fatally failed.
+        let _ = fs::remove_file(temp_file).await;
+        return Attempt::Retryable(None);
+    }
+    drop(out);
+
+    let digest = to_hex(&hasher.finalize());
+
+    // If the server publishes a sibling digest, treat a mismatch as a failed
+    // transfer so the retry loop re-fetches rather than trusting bad bytes. A
+    // missing digest is a legitimate skip. A transient fetch failure is retried
+    // in place — the body is already complete, so we re-fetch only the digest a
+    // few times rather than discarding a good download and re-transferring it.
+    let mut remote = fetch_remote_digest(client, url, hash_alg, log).await;
+    for attempt in 0..MAX_RETRIES {
+        if !matches!(remote, RemoteDigest::Unavailable) {
+            break;
+        }
+        let delay = jittered_backoff(attempt);
+        log.debug(&format!(
+            "[{}/{total}] Re-fetching sibling digest for {file_path} after {}ms",
+            progress.load(Ordering::Relaxed) + 1,
+            delay.as_millis()
+        ));
+        tokio::time::sleep(delay).await;
+        remote = fetch_remote_digest(client, url, hash_alg, log).await;
+    }
+    match remote {
+        RemoteDigest::Found(expected) if !expected.eq_ignore_ascii_case(&digest) => {
+            let n = progress.load(Ordering::Relaxed) + 1;
+            log.error(&format!(
+                "[{n}/{total}] Checksum mismatch for {file_path}: expected {expected}, got {digest}"
+            ));
+            let _ = fs::remove_file(temp_file).await;
+            return Attempt::Retryable(None);
+        }
+        RemoteDigest::Found(_) => log.debug(&format!("Checksum verified for {file_path}")),
+        RemoteDigest::Absent => {}
+        // Digest still unreachable after retries: accept the complete body
+        // rather than throw away a good download. Verification is skipped.
+        RemoteDigest::Unavailable => log.debug(&format!(
+            "Sibling digest unavailable for {file_path}, accepting unverified"
+        )),
     }
 
     if let Err(e) = fs::rename(temp_file, local_file).await {
-        let n = progress.fetch_add(1, Ordering::Relaxed) + 1;
+        let n = progress.load(Ordering::Relaxed) + 1;
         log.error(&format!("[{n}/{total}] Failed moving {file_path}: {e}"));
         let _ = fs::remove_file(temp_file).await;
-        return DlResult::Failed;
+        return Attempt::Retryable(None);
     }
 
-    save_metadata(meta_file, &etag, &last_modified).await;
+    save_metadata(meta_file, &etag, &last_modified, &digest, hash_alg.ext()).await;
 
-    let n = progress.fetch_add(1, Ordering::Relaxed) + 1;
+    let n = progress.load(Ordering::Relaxed) + 1;
     log.info(&format!("[{n}/{total}] ⬇️  {GREEN}Downloaded{NC}: {file_path}"));
-    DlResult::Downloaded
+    Attempt::Done(DlResult::Downloaded)
+}
+
+// ── Cycle orchestration ───────────────────────────────────────────────────────
+
+struct CycleSummary {
+    downloaded: usize,
+    skipped: usize,
+    failed: usize,
+}
+
+/// Crawl the tree once, driving the spinner while enumeration is in flight.
+async fn enumerate_with_spinner(
+    client: &reqwest::Client,
+    base_url: &str,
+    log: &Arc<Logger>,
+    max_concurrent: usize,
+    spinner: bool,
+) -> Vec<FileEntry> {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let (stop_tx, stop_rx) = tokio::sync::watch::channel(false);
+
+    let spinner_handle = if spinner {
+        log.spinner_active.store(true, Ordering::Relaxed);
+        let c = counter.clone();
+        Some(tokio::spawn(async move { run_spinner(c, stop_rx).await }))
+    } else {
+        drop(stop_rx);
+        None
+    };
+
+    let files = enumerate_files(client, base_url, log, &counter, max_concurrent).await;
+
+    if let Some(h) = spinner_handle {
+        let _ = stop_tx.send(true);
+        let _ = h.await;
+        log.spinner_active.store(false, Ordering::Relaxed);
+    }
+
+    files
+}
+
+/// Download an already-enumerated file list, returning a per-cycle summary.
+///
+/// When `cancel` fires, files not yet started are skipped, but downloads that
+/// already hold a concurrency permit are awaited to completion so the in-flight
+/// batch drains cleanly instead of abandoning half-written `.tmp` files.
+#[allow(clippy::too_many_arguments)]
+async fn download_all(
+    client: &reqwest::Client,
+    files: Vec<FileEntry>,
+    download_dir: &Path,
+    metadata_dir: &Path,
+    hash_alg: HashAlg,
+    max_concurrent: usize,
+    spinner: bool,
+    log: &Arc<Logger>,
+    cancel: Option<tokio::sync::watch::Receiver<bool>>,
+) -> CycleSummary {
+    let total = files.len();
+    // Grand total from the per-entry sizes Caddy reported during enumeration,
+    // so the spinner can show a true percentage-complete and ETA.
+    let total_bytes: u64 = files.iter().map(|f| f.size).sum();
+    let progress = Arc::new(AtomicUsize::new(0));
+    let bytes = Arc::new(AtomicU64::new(0));
+    let sem = Arc::new(Semaphore::new(max_concurrent));
+
+    let (stop_tx, stop_rx) = tokio::sync::watch::channel(false);
+    let spinner_handle = if spinner {
+        log.spinner_active.store(true, Ordering::Relaxed);
+        let (p, b) = (progress.clone(), bytes.clone());
+        Some(tokio::spawn(async move {
+            run_download_spinner(p, b, total, total_bytes, stop_rx).await
+        }))
+    } else {
+        drop(stop_rx);
+        None
+    };
+
+    let mut handles = Vec::with_capacity(total);
+
+    for entry in files {
+        let client = client.clone();
+        let download_dir = download_dir.to_path_buf();
+        let metadata_dir = metadata_dir.to_path_buf();
+        let progress = progress.clone();
+        let bytes = bytes.clone();
+        let sem = sem.clone();
+        let log = log.clone();
+        let mut cancel = cancel.clone();
+
+        handles.push(tokio::spawn(async move {
+            // Wait for a permit, but give up if shutdown is requested first — a
+            // file that never acquired a permit is not in-flight, so skipping it
+            // leaves no `.tmp` behind. Files already past this point hold a
+            // permit and run `download_file` to completion.
+            let _permit = tokio::select! {
+                biased;
+                _ = wait_for_cancel(&mut cancel) => return DlResult::Skipped,
+                permit = sem.acquire() => match permit {
+                    Ok(p) => p,
+                    Err(_) => return DlResult::Failed,
+                },
+            };
+            download_file(
+                &client,
+                &entry.path,
+                &entry.url,
+                &download_dir,
+                &metadata_dir,
+                hash_alg,
+                &progress,
+                &bytes,
+                total,
+                &log,
+            )
+            .await
+        }));
+    }
+
+    let mut summary = CycleSummary { downloaded: 0, skipped: 0, failed: 0 };
+    for h in handles {
+        match h.await {
+            Ok(DlResult::Downloaded) => summary.downloaded += 1,
+            Ok(DlResult::Skipped) => summary.skipped += 1,
+            _ => summary.failed += 1,
+        }
+    }
+
+    if let Some(h) = spinner_handle {
+        let _ = stop_tx.send(true);
+        let _ = h.await;
+        log.spinner_active.store(false, Ordering::Relaxed);
+    }
+
+    summary
+}
+
+/// Resolve once the shared cancellation flag turns true. With no channel
+/// (single-pass mode) it never resolves, so the `select!` always takes the
+/// permit branch and nothing is skipped.
+async fn wait_for_cancel(cancel: &mut Option<tokio::sync::watch::Receiver<bool>>) {
+    match cancel {
+        Some(rx) => {
+            if *rx.borrow() {
+                return;
+            }
+            while rx.changed().await.is_ok() {
+                if *rx.borrow() {
+                    return;
+                }
+            }
+            // Sender dropped without ever signalling: nothing more to wait for.
+            std::future::pending::<()>().await
+        }
+        None => std::future::pending::<()>().await,
+    }
+}
+
+/// Resolve when the process is asked to stop (SIGINT or SIGTERM), so watch
+/// mode can finish the in-flight batch before exiting.
+async fn shutdown_signal() {
+    let ctrl_c = tokio::signal::ctrl_c();
+    #[cfg(unix)]
+    {
+        let mut term = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler");
+        tokio::select! {
+            _ = ctrl_c => {}
+            _ = term.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = ctrl_c.await;
+    }
 }
 
 // ── Main ────────────────────────────────────────────────────────────────────
@@ -429,27 +1355,113 @@ async fn main() {
         .build()
         .expect("Failed to create HTTP client");
 
-    // ── Enumerate ───────────────────────────────────────────────────────
+    let spinner = args.verbose == 0;
 
-    let counter = Arc::new(AtomicUsize::new(0));
-    let (stop_tx, stop_rx) = tokio::sync::watch::channel(false);
+    // ── Verify ──────────────────────────────────────────────────────────
 
-    let spinner_handle = if args.verbose == 0 {
-        log.spinner_active.store(true, Ordering::Relaxed);
-        let c = counter.clone();
-        Some(tokio::spawn(async move { run_spinner(c, stop_rx).await }))
+    if args.verify {
+        // Drive verification from the local `.meta` sidecars, not a remote
+        // crawl: bitrot is a local concern, so the check must run offline and
+        // cover files that have since been removed from the server.
+        let files = enumerate_local_files(&metadata_dir).await;
+        let total = files.len();
+        log.info(&format!("Verifying {total} local files against stored digests..."));
+        let corrupt = verify_files(&files, &download_dir, &metadata_dir, args.hash, &log).await;
+        if corrupt > 0 {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // ── Watch mode ──────────────────────────────────────────────────────
+    //
+    // A long-lived loop that keeps the local tree in step with the remote,
+    // leaning on the persisted `.metadata` so unchanged files short-circuit on
+    // `304 Not Modified`. On SIGINT/SIGTERM we stop enqueuing new files and let
+    // the in-flight batch drain to completion before exiting, so no half-written
+    // `.tmp` files are left behind.
+    if let Some(interval) = args.watch {
+        log.info(&format!("Watch mode: mirroring every {interval}s (Ctrl-C to stop)"));
+        let interval = std::time::Duration::from_secs(interval);
+        // Pin the signal future once and poll it from the very first cycle so
+        // the SIGINT/SIGTERM handlers are installed before any work starts —
+        // polling it only between cycles would let a Ctrl-C during the first
+        // enumerate/download hit the default disposition and kill us outright.
+        let mut shutdown = Box::pin(shutdown_signal());
+        let mut cycle = 0usize;
+        loop {
+            cycle += 1;
+            // A per-cycle cancellation flag: when the signal fires mid-batch we
+            // flip it so `download_all` stops starting new files, then await the
+            // cycle so the already-running downloads finish cleanly.
+            let (cancel_tx, cancel_rx) = tokio::sync::watch::channel(false);
+            let mut cycle_work = Box::pin(async {
+                let files =
+                    enumerate_with_spinner(&client, &base_url, &log, max_concurrent, spinner).await;
+                download_all(
+                    &client, files, &download_dir, &metadata_dir, args.hash, max_concurrent,
+                    spinner, &log, Some(cancel_rx),
+                )
+                .await
+            });
+
+            let summary = tokio::select! {
+                biased;
+                _ = &mut shutdown => {
+                    log.info("Received shutdown signal, draining in-flight downloads...");
+                    let _ = cancel_tx.send(true);
+                    let summary = cycle_work.await;
+                    log.info(&format!(
+                        "Cycle {cycle}: {} downloaded, {} unchanged or not started, {} failed",
+                        summary.downloaded, summary.skipped, summary.failed
+                    ));
+                    log.info("Exiting watch mode");
+                    break;
+                }
+                summary = &mut cycle_work => summary,
+            };
+            log.info(&format!(
+                "Cycle {cycle}: {} downloaded, {} unchanged, {} failed",
+                summary.downloaded, summary.skipped, summary.failed
+            ));
+
+            tokio::select! {
+                biased;
+                _ = &mut shutdown => {
+                    log.info("Received shutdown signal, exiting watch mode");
+                    break;
+                }
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+        return;
+    }
+
+    // ── Single pass ─────────────────────────────────────────────────────
+
+    // `--resume` loads the cached listing and skips straight to downloading;
+    // otherwise crawl fresh and persist the manifest for next time. Pruning
+    // needs an authoritative fresh enumeration, so it overrides `--resume`.
+    let cached = if args.resume && !args.prune {
+        load_manifest(&metadata_dir, &base_url, &log).await
     } else {
-        drop(stop_rx);
+        if args.resume && args.prune {
+            log.info("--prune requires a fresh enumeration; ignoring --resume");
+        }
         None
     };
 
-    let files = enumerate_files(&client, &base_url, &log, &counter, max_concurrent).await;
-
-    if let Some(h) = spinner_handle {
-        let _ = stop_tx.send(true);
-        let _ = h.await;
-        log.spinner_active.store(false, Ordering::Relaxed);
-    }
+    let files = match cached {
+        Some(files) => files,
+        None => {
+            let files = enumerate_with_spinner(&client, &base_url, &log, max_concurrent, spinner).await;
+            save_manifest(&metadata_dir, &base_url, &files, &log).await;
+            if args.prune {
+                prune_tree(&files, &download_dir, &metadata_dir, &log).await;
+            }
+            files
+        }
+    };
 
     let total = files.len();
     log.info(&format!("Found {total} files to process"));
@@ -459,56 +1471,85 @@ async fn main() {
         return;
     }
 
-    // ── Download ────────────────────────────────────────────────────────
-
     log.info(&format!("Downloading {total} files with max {max_concurrent} concurrent requests..."));
 
-    let progress = Arc::new(AtomicUsize::new(0));
-    let sem = Arc::new(Semaphore::new(max_concurrent));
+    let summary = download_all(
+        &client, files, &download_dir, &metadata_dir, args.hash, max_concurrent, spinner, &log, None,
+    )
+    .await;
 
-    let mut handles = Vec::with_capacity(total);
+    log.info(&format!(
+        "Mirror complete: {} downloaded, {} unchanged, {} failed",
+        summary.downloaded, summary.skipped, summary.failed
+    ));
 
-    for (file_path, url) in files {
-        let client = client.clone();
-        let download_dir = download_dir.clone();
-        let metadata_dir = metadata_dir.clone();
-        let progress = progress.clone();
-        let sem = sem.clone();
-        let log = log.clone();
+    if summary.failed > 0 {
+        std::process::exit(1);
+    }
+}
 
-        handles.push(tokio::spawn(async move {
-            let Ok(_permit) = sem.acquire().await else {
-                return DlResult::Failed;
-            };
-            download_file(
-                &client,
-                &file_path,
-                &url,
-                &download_dir,
-                &metadata_dir,
-                &progress,
-                total,
-                &log,
-            )
-            .await
-        }));
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_after_parses_delta_seconds() {
+        // A value under the cap passes through verbatim.
+        assert_eq!(
+            parse_retry_after("5"),
+            Some(std::time::Duration::from_secs(5))
+        );
+        assert_eq!(parse_retry_after("  0 "), Some(std::time::Duration::ZERO));
+        // A value above the cap is clamped so a worker can't park for an hour.
+        assert_eq!(
+            parse_retry_after("3600"),
+            Some(std::time::Duration::from_millis(BACKOFF_CAP_MS))
+        );
     }
 
-    let mut downloaded = 0usize;
-    let mut skipped = 0usize;
-    let mut failed = 0usize;
+    #[test]
+    fn retry_after_parses_http_date() {
+        // A date far in the past yields no positive delay.
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            None
+        );
+        // A well-formed but future-ish date parses to some delay.
+        assert!(parse_retry_after("Fri, 01 Jan 2100 00:00:00 GMT").is_some());
+        assert!(parse_retry_after("not a date").is_none());
+    }
 
-    for h in handles {
-        match h.await {
-            Ok(DlResult::Downloaded) => downloaded += 1,
-            Ok(DlResult::Skipped) => skipped += 1,
-            _ => failed += 1,
+    #[test]
+    fn content_range_start_parses_offset() {
+        assert_eq!(parse_content_range_start("bytes 200-1023/1024"), Some(200));
+        assert_eq!(parse_content_range_start("bytes 0-0/1"), Some(0));
+        assert_eq!(parse_content_range_start("bytes */1024"), None);
+        assert_eq!(parse_content_range_start("200-1023/1024"), None);
+    }
+
+    #[test]
+    fn backoff_stays_within_cap() {
+        for attempt in 0..40u32 {
+            let d = jittered_backoff(attempt);
+            assert!(d.as_millis() as u64 <= BACKOFF_CAP_MS);
         }
     }
 
-    log.info(&format!("Mirror complete: {downloaded} downloaded, {skipped} unchanged, {failed} failed"));
+    #[test]
+    fn human_bytes_scales_units() {
+        assert_eq!(human_bytes(0), "0 B");
+        assert_eq!(human_bytes(512), "512 B");
+        assert_eq!(human_bytes(1024), "1.0 KiB");
+        assert_eq!(human_bytes(1536), "1.5 KiB");
+        assert_eq!(human_bytes(1024 * 1024), "1.0 MiB");
+    }
 
-    if failed > 0 {
-        std::process::exit(1);
+    #[test]
+    fn human_duration_drops_empty_leads() {
+        assert_eq!(human_duration(45.0), "45s");
+        assert_eq!(human_duration(125.0), "2m05s");
+        assert_eq!(human_duration(3661.0), "1h01m");
     }
 }